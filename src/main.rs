@@ -1,7 +1,7 @@
 use std::env;
 use csv::ReaderBuilder;
 use std::fs::{self, File};
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
 use std::error::Error;
 use std::path::{Path, PathBuf};
 use std::collections::HashMap;
@@ -10,7 +10,146 @@ use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
 use std::fs::DirEntry;
 
-fn calculate_total_size(path: &Path) -> io::Result<u64> {
+// A symlink hop chain longer than this is treated as pathological rather
+// than followed indefinitely.
+const MAX_SYMLINK_HOPS: usize = 20;
+
+#[derive(Debug, Clone)]
+enum SymlinkErrorType {
+    /// The link (directly or via a chain) resolves back into a directory
+    /// we're already descending into.
+    InfiniteRecursion,
+    /// The link's target does not exist on disk.
+    BrokenTarget,
+    /// The link chain is longer than `MAX_SYMLINK_HOPS`.
+    TooManyHops,
+}
+
+impl std::fmt::Display for SymlinkErrorType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SymlinkErrorType::InfiniteRecursion => write!(f, "infinite recursion"),
+            SymlinkErrorType::BrokenTarget => write!(f, "broken target"),
+            SymlinkErrorType::TooManyHops => write!(f, "too many symlink hops"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SymlinkInfo {
+    destination: PathBuf,
+    error_type: SymlinkErrorType,
+}
+
+/// Follows a chain of symlinks (relative targets resolved against the
+/// link's own directory) up to `MAX_SYMLINK_HOPS`, returning the first
+/// non-symlink target found.
+fn resolve_symlink_hops(link_path: &Path) -> io::Result<PathBuf> {
+    let mut current = link_path.to_path_buf();
+
+    for _ in 0..MAX_SYMLINK_HOPS {
+        let target = fs::read_link(&current)?;
+        let resolved = if target.is_absolute() {
+            target
+        } else {
+            current.parent().unwrap_or_else(|| Path::new(".")).join(target)
+        };
+
+        let meta = fs::symlink_metadata(&resolved)?;
+        if meta.file_type().is_symlink() {
+            current = resolved;
+            continue;
+        }
+        return Ok(resolved);
+    }
+
+    Err(io::Error::other("too many symlink hops"))
+}
+
+/// Exclude-glob and hidden-file filtering shared by the recursive walk and
+/// the first-layer scan. Patterns without a `/` match the entry's basename
+/// at any depth; patterns containing `/` match the full path relative to
+/// the scan root.
+#[derive(Debug, Clone, Default)]
+struct WalkOptions {
+    exclude: Vec<String>,
+    show_hidden: bool,
+}
+
+impl WalkOptions {
+    fn is_excluded(&self, root: &Path, path: &Path) -> bool {
+        let relative = path.strip_prefix(root).unwrap_or(path).display().to_string().replace('\\', "/");
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+
+        if !self.show_hidden && name.starts_with('.') {
+            return true;
+        }
+
+        self.exclude.iter().any(|pattern| {
+            if pattern.contains('/') {
+                glob_match(pattern, &relative)
+            } else {
+                glob_match(pattern, name)
+            }
+        })
+    }
+
+    /// A signature covering every option that affects which entries a walk
+    /// includes, so a cached aggregate computed under one set of
+    /// exclude/hidden settings is never reused under a different one.
+    fn cache_key(&self) -> String {
+        let mut exclude = self.exclude.clone();
+        exclude.sort();
+        format!("{}|{}", exclude.join(","), self.show_hidden)
+    }
+}
+
+/// Minimal shell-style glob matcher: `*` matches any run of characters
+/// except `/`, `**` matches across `/` as well.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    fn matches(pattern: &[u8], text: &[u8]) -> bool {
+        if pattern.is_empty() {
+            return text.is_empty();
+        }
+
+        if pattern[0] == b'*' {
+            if pattern.len() > 1 && pattern[1] == b'*' {
+                // `**/` also matches zero path segments, so a leading `/`
+                // right after `**` is optional.
+                let rest = &pattern[2..];
+                let rest_no_slash = rest.strip_prefix(b"/").unwrap_or(rest);
+                return (0..=text.len()).any(|i| matches(rest, &text[i..]) || matches(rest_no_slash, &text[i..]));
+            }
+
+            let rest = &pattern[1..];
+            for i in 0..=text.len() {
+                if matches(rest, &text[i..]) {
+                    return true;
+                }
+                if i < text.len() && text[i] == b'/' {
+                    break;
+                }
+            }
+            return false;
+        }
+
+        if text.is_empty() || pattern[0] != text[0] {
+            return false;
+        }
+
+        matches(&pattern[1..], &text[1..])
+    }
+
+    matches(pattern.as_bytes(), text.as_bytes())
+}
+
+fn calculate_total_size(
+    path: &Path,
+    root: &Path,
+    stack: &mut Vec<PathBuf>,
+    symlinks: &mut Vec<(PathBuf, SymlinkInfo)>,
+    options: &WalkOptions,
+) -> io::Result<u64> {
     let mut total_size = 0;
     let entries = match fs::read_dir(path) {
         Ok(entries) => entries,
@@ -22,37 +161,189 @@ fn calculate_total_size(path: &Path) -> io::Result<u64> {
     };
 
     for entry in entries {
-        match entry {
-            Ok(entry) => {
-                let metadata = match entry.metadata() {
-                    Ok(metadata) => metadata,
-                    Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
-                        eprintln!("Skipping file due to permission error: {}", entry.path().display());
-                        continue;
-                    }
-                    Err(e) => return Err(e),
-                };
-
-                if metadata.is_dir() {
-                    total_size += calculate_total_size(&entry.path())?;
-                } else {
-                    total_size += metadata.len();
-                }
-            }
+        let entry = match entry {
+            Ok(entry) => entry,
             Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
                 eprintln!("Skipping entry due to permission error: {}", path.display());
                 continue;
             }
             Err(e) => return Err(e),
+        };
+
+        let entry_path = entry.path();
+        if options.is_excluded(root, &entry_path) {
+            continue;
+        }
+
+        let sym_meta = match fs::symlink_metadata(&entry_path) {
+            Ok(meta) => meta,
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+                eprintln!("Skipping file due to permission error: {}", entry_path.display());
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+
+        if sym_meta.file_type().is_symlink() {
+            match resolve_symlink_hops(&entry_path) {
+                Ok(resolved) if resolved.is_dir() => {
+                    let canon = resolved.canonicalize().unwrap_or_else(|_| resolved.clone());
+                    if stack.contains(&canon) {
+                        symlinks.push((
+                            entry_path,
+                            SymlinkInfo { destination: resolved, error_type: SymlinkErrorType::InfiniteRecursion },
+                        ));
+                        continue;
+                    }
+                    stack.push(canon);
+                    total_size += calculate_total_size(&resolved, root, stack, symlinks, options)?;
+                    stack.pop();
+                }
+                Ok(resolved) => {
+                    total_size += fs::metadata(&resolved).map(|m| m.len()).unwrap_or(0);
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    let destination = fs::read_link(&entry_path).unwrap_or_default();
+                    symlinks.push((
+                        entry_path,
+                        SymlinkInfo { destination, error_type: SymlinkErrorType::BrokenTarget },
+                    ));
+                }
+                Err(_) => {
+                    symlinks.push((
+                        entry_path,
+                        SymlinkInfo { destination: PathBuf::new(), error_type: SymlinkErrorType::TooManyHops },
+                    ));
+                }
+            }
+        } else if sym_meta.is_dir() {
+            stack.push(entry_path.canonicalize().unwrap_or_else(|_| entry_path.clone()));
+            total_size += calculate_total_size(&entry_path, root, stack, symlinks, options)?;
+            stack.pop();
+        } else {
+            total_size += sym_meta.len();
         }
     }
 
     Ok(total_size)
 }
-// updated first layer full size 
-fn get_first_layer_full_sizes(start_path: &Path) -> io::Result<HashMap<PathBuf, u64>> {
+/// Persisted aggregate size for a first-layer directory, keyed by its
+/// canonical path. `mtime` is the latest modification time found anywhere
+/// in that directory's subtree (see `subtree_latest_mtime`), not just the
+/// directory's own mtime, so an edit to a deeply nested file still busts
+/// the cache even though it leaves every ancestor directory's own mtime
+/// untouched. `options_key` is `WalkOptions::cache_key()` for the walk that
+/// produced `total_size`, so a later run with different exclude/hidden
+/// settings recomputes instead of reusing a total filtered differently.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct CacheEntry {
+    mtime: u64,
+    options_key: String,
+    total_size: u64,
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct SizeCache {
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+fn size_cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("explain").join("size_cache.json"))
+}
+
+fn load_size_cache() -> SizeCache {
+    let Some(path) = size_cache_path() else {
+        return SizeCache::default();
+    };
+
+    fs::read_to_string(&path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_size_cache(cache: &SizeCache) -> io::Result<()> {
+    let Some(path) = size_cache_path() else {
+        return Ok(());
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let contents = serde_json::to_string_pretty(cache).unwrap_or_default();
+    fs::write(path, contents)
+}
+
+fn mtime_secs(meta: &fs::Metadata) -> io::Result<u64> {
+    let modified = meta.modified()?;
+    Ok(modified.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs())
+}
+
+fn dir_mtime(path: &Path) -> io::Result<u64> {
+    mtime_secs(&fs::metadata(path)?)
+}
+
+/// Recursively finds the most recent modification time anywhere under
+/// `path`, including `path` itself. A directory's own mtime only changes
+/// when an entry is added to or removed from it directly, so comparing just
+/// that value misses edits to files nested further down; walking the whole
+/// subtree is the only reliable way to notice those.
+fn subtree_latest_mtime(path: &Path, root: &Path, options: &WalkOptions) -> io::Result<u64> {
+    let mut latest = dir_mtime(path)?;
+
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => return Ok(latest),
+        Err(e) => return Err(e),
+    };
+
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied => continue,
+            Err(e) => return Err(e),
+        };
+
+        let entry_path = entry.path();
+        if options.is_excluded(root, &entry_path) {
+            continue;
+        }
+
+        let meta = match fs::symlink_metadata(&entry_path) {
+            Ok(meta) => meta,
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied => continue,
+            Err(e) => return Err(e),
+        };
+
+        if meta.is_dir() {
+            latest = latest.max(subtree_latest_mtime(&entry_path, root, options)?);
+        } else if let Ok(secs) = mtime_secs(&meta) {
+            latest = latest.max(secs);
+        }
+    }
+
+    Ok(latest)
+}
+
+/// Per-entry size plus whatever symlink issues were hit while computing it,
+/// and a cache update if the scan found this entry's recursive signature
+/// had changed (or it wasn't cached yet).
+type FirstLayerEntryResult = (PathBuf, u64, Vec<(PathBuf, SymlinkInfo)>, Option<(PathBuf, CacheEntry)>);
+
+/// Sizes keyed by first-layer entry, any symlink issues keyed the same way,
+/// and the cache entries (if any) that need writing back to disk.
+type FirstLayerScanResult = (HashMap<PathBuf, u64>, HashMap<PathBuf, Vec<SymlinkInfo>>, Vec<(PathBuf, CacheEntry)>);
+
+// updated first layer full size
+fn get_first_layer_full_sizes(
+    start_path: &Path,
+    options: &WalkOptions,
+    cache: Option<&SizeCache>,
+) -> io::Result<FirstLayerScanResult> {
     let entries: Vec<DirEntry> = fs::read_dir(start_path)?
         .filter_map(|entry| entry.ok())
+        .filter(|entry| !options.is_excluded(start_path, &entry.path()))
         .collect();
 
     let pb = ProgressBar::new(entries.len() as u64);
@@ -63,22 +354,433 @@ fn get_first_layer_full_sizes(start_path: &Path) -> io::Result<HashMap<PathBuf,
     );
     pb.enable_steady_tick(100);
 
-    let folder_sizes: HashMap<PathBuf, u64> = entries
+    let root_canon = start_path.canonicalize().unwrap_or_else(|_| start_path.to_path_buf());
+    let results: Vec<FirstLayerEntryResult> = entries
         .into_par_iter() // Converts Vec<DirEntry> to a Rayon parallel iterator
         .filter_map(|entry| {
-            let metadata = entry.metadata().ok()?;
-            if metadata.is_dir() {
-                let size = calculate_total_size(&entry.path()).ok()?;
-                Some((entry.path(), size))
+            let entry_path = entry.path();
+            let sym_meta = fs::symlink_metadata(&entry_path).ok()?;
+            let mut symlinks = Vec::new();
+
+            if sym_meta.file_type().is_symlink() {
+                match resolve_symlink_hops(&entry_path) {
+                    Ok(resolved) if resolved.is_dir() => {
+                        let mut stack = vec![root_canon.clone(), resolved.canonicalize().unwrap_or_else(|_| resolved.clone())];
+                        let size = calculate_total_size(&resolved, start_path, &mut stack, &mut symlinks, options).ok()?;
+                        Some((entry_path, size, symlinks, None))
+                    }
+                    Ok(resolved) => {
+                        let size = fs::metadata(&resolved).map(|m| m.len()).unwrap_or(0);
+                        Some((entry_path, size, symlinks, None))
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                        let destination = fs::read_link(&entry_path).unwrap_or_default();
+                        symlinks.push((entry_path.clone(), SymlinkInfo { destination, error_type: SymlinkErrorType::BrokenTarget }));
+                        Some((entry_path, 0, symlinks, None))
+                    }
+                    Err(_) => {
+                        symlinks.push((entry_path.clone(), SymlinkInfo { destination: PathBuf::new(), error_type: SymlinkErrorType::TooManyHops }));
+                        Some((entry_path, 0, symlinks, None))
+                    }
+                }
+            } else if sym_meta.is_dir() {
+                let canon = entry_path.canonicalize().unwrap_or_else(|_| entry_path.clone());
+                // Only bother computing the recursive signature when a cache
+                // was actually requested, so a cache-less run never produces
+                // an update entry.
+                let current_mtime = cache.and_then(|_| subtree_latest_mtime(&entry_path, start_path, options).ok());
+
+                let options_key = options.cache_key();
+
+                if let (Some(cache), Some(mtime)) = (cache, current_mtime) {
+                    if let Some(cached) = cache.entries.get(&canon) {
+                        if cached.mtime == mtime && cached.options_key == options_key {
+                            return Some((entry_path, cached.total_size, symlinks, None));
+                        }
+                    }
+                }
+
+                let mut stack = vec![root_canon.clone(), canon.clone()];
+                let size = calculate_total_size(&entry_path, start_path, &mut stack, &mut symlinks, options).ok()?;
+                let update = current_mtime.map(|mtime| (canon, CacheEntry { mtime, options_key, total_size: size }));
+                Some((entry_path, size, symlinks, update))
             } else {
-                Some((entry.path(), metadata.len()))
+                Some((entry_path, sym_meta.len(), symlinks, None))
             }
         })
         .inspect(|_| pb.inc(1))
         .collect();
 
     pb.finish_and_clear();
-    Ok(folder_sizes)
+
+    let mut folder_sizes = HashMap::new();
+    let mut symlink_issues: HashMap<PathBuf, Vec<SymlinkInfo>> = HashMap::new();
+    let mut cache_updates = Vec::new();
+    for (path, size, issues, update) in results {
+        folder_sizes.insert(path.clone(), size);
+        if let Some(update) = update {
+            cache_updates.push(update);
+        }
+        if !issues.is_empty() {
+            symlink_issues.entry(path).or_default().extend(issues.into_iter().map(|(_, info)| info));
+        }
+    }
+
+    Ok((folder_sizes, symlink_issues, cache_updates))
+}
+
+// Duplicate-file detection: bucket by size first (a unique size can never
+// collide), then by a partial hash of the leading block, then by a full
+// hash, so large unrelated files are never read in full.
+const DUPLICATE_PREFIX_LEN: u64 = 4096;
+
+fn collect_files_by_size(path: &Path, sizes: &mut HashMap<u64, Vec<PathBuf>>) -> io::Result<()> {
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+            eprintln!("Skipping directory due to permission error: {}", path.display());
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    };
+
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied => continue,
+            Err(e) => return Err(e),
+        };
+
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+                eprintln!("Skipping file due to permission error: {}", entry.path().display());
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+
+        if metadata.is_dir() {
+            collect_files_by_size(&entry.path(), sizes)?;
+        } else {
+            sizes.entry(metadata.len()).or_default().push(entry.path());
+        }
+    }
+
+    Ok(())
+}
+
+fn hash_file(path: &Path, limit: Option<u64>) -> io::Result<u64> {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::Hasher;
+
+    let mut file = File::open(path)?;
+    let mut hasher = DefaultHasher::new();
+    let mut buf = [0u8; 8192];
+    let mut remaining = limit;
+
+    loop {
+        let to_read = match remaining {
+            Some(0) => break,
+            Some(r) => buf.len().min(r as usize),
+            None => buf.len(),
+        };
+        let read = file.read(&mut buf[..to_read])?;
+        if read == 0 {
+            break;
+        }
+        hasher.write(&buf[..read]);
+        if let Some(r) = remaining.as_mut() {
+            *r -= read as u64;
+        }
+    }
+
+    Ok(hasher.finish())
+}
+
+/// Groups byte-identical files under `start_path`, skipping any size bucket
+/// that can't possibly contain a duplicate.
+fn find_duplicate_files(start_path: &Path) -> io::Result<Vec<Vec<PathBuf>>> {
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    collect_files_by_size(start_path, &mut by_size)?;
+
+    let mut duplicate_groups = Vec::new();
+
+    for (_, paths) in by_size.into_iter().filter(|(_, paths)| paths.len() > 1) {
+        let partial_hashes: Vec<(PathBuf, io::Result<u64>)> = paths
+            .into_par_iter()
+            .map(|path| {
+                let hash = hash_file(&path, Some(DUPLICATE_PREFIX_LEN));
+                (path, hash)
+            })
+            .collect();
+
+        let mut by_partial_hash: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+        for (path, hash) in partial_hashes {
+            match hash {
+                Ok(hash) => by_partial_hash.entry(hash).or_default().push(path),
+                Err(e) => eprintln!("Skipping {} due to read error: {}", path.display(), e),
+            }
+        }
+
+        for (_, paths) in by_partial_hash.into_iter().filter(|(_, paths)| paths.len() > 1) {
+            let full_hashes: Vec<(PathBuf, io::Result<u64>)> = paths
+                .into_par_iter()
+                .map(|path| {
+                    let hash = hash_file(&path, None);
+                    (path, hash)
+                })
+                .collect();
+
+            let mut by_full_hash: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+            for (path, hash) in full_hashes {
+                match hash {
+                    Ok(hash) => by_full_hash.entry(hash).or_default().push(path),
+                    Err(e) => eprintln!("Skipping {} due to read error: {}", path.display(), e),
+                }
+            }
+
+            for (_, paths) in by_full_hash.into_iter().filter(|(_, paths)| paths.len() > 1) {
+                duplicate_groups.push(paths);
+            }
+        }
+    }
+
+    Ok(duplicate_groups)
+}
+
+fn run_duplicates_mode(dir: &Path) -> io::Result<()> {
+    let groups = find_duplicate_files(dir)?;
+
+    if groups.is_empty() {
+        println!("No duplicate files found under {}", dir.display());
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.add_row(Row::new(vec![
+        Cell::new("#"),
+        Cell::new("Files"),
+        Cell::new("Size Each"),
+        Cell::new("Reclaimable"),
+    ]));
+
+    let mut total_reclaimable: u64 = 0;
+
+    for (index, paths) in groups.iter().enumerate() {
+        let size = fs::metadata(&paths[0]).map(|m| m.len()).unwrap_or(0);
+        let reclaimable = size * (paths.len() as u64 - 1);
+        total_reclaimable += reclaimable;
+
+        let file_list = paths
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        table.add_row(Row::new(vec![
+            Cell::new(&(index + 1).to_string()),
+            Cell::new(&file_list),
+            Cell::new(&format_size(size)),
+            Cell::new(&format_size(reclaimable)),
+        ]));
+    }
+
+    table.printstd();
+    println!("\n{} duplicate group(s), {} reclaimable\n", groups.len(), format_size(total_reclaimable));
+
+    Ok(())
+}
+
+// Uncompressed tar (USTAR) export: walks the tree once, streaming a header
+// plus padded file bytes per entry, then the two zero blocks that mark the
+// end of the archive.
+const TAR_BLOCK_SIZE: u64 = 512;
+
+fn collect_archive_entries(dir: &Path, stack: &mut Vec<PathBuf>, entries: &mut Vec<PathBuf>) -> io::Result<()> {
+    let read_dir = match fs::read_dir(dir) {
+        Ok(read_dir) => read_dir,
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+            eprintln!("Skipping directory due to permission error: {}", dir.display());
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    };
+
+    for entry in read_dir {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied => continue,
+            Err(e) => return Err(e),
+        };
+
+        let path = entry.path();
+        let meta = match fs::symlink_metadata(&path) {
+            Ok(meta) => meta,
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied => continue,
+            Err(e) => return Err(e),
+        };
+
+        if meta.file_type().is_symlink() {
+            eprintln!("Skipping symlink (not archived): {}", path.display());
+            continue;
+        }
+
+        entries.push(path.clone());
+
+        if meta.is_dir() {
+            let canon = path.canonicalize().unwrap_or_else(|_| path.clone());
+            if stack.contains(&canon) {
+                continue;
+            }
+            stack.push(canon);
+            collect_archive_entries(&path, stack, entries)?;
+            stack.pop();
+        }
+    }
+
+    Ok(())
+}
+
+fn set_tar_field(buf: &mut [u8], value: &[u8]) {
+    let len = value.len().min(buf.len());
+    buf[..len].copy_from_slice(&value[..len]);
+}
+
+fn set_tar_octal(buf: &mut [u8], value: u64) {
+    let width = buf.len() - 1;
+    set_tar_field(buf, format!("{:0width$o}", value, width = width).as_bytes());
+}
+
+/// Splits a relative archive path into USTAR's 100-byte `name` and 155-byte
+/// `prefix` fields (prefix at header offset 345), picking the earliest `/`
+/// boundary that leaves both pieces within their size limits so `name`
+/// keeps as much of the path as possible. Returns `None` if the path has no
+/// such split, meaning it's too long to represent in the classic USTAR
+/// format at all.
+fn split_tar_name(path: &str) -> Option<(String, String)> {
+    if path.len() <= 100 {
+        return Some((String::new(), path.to_string()));
+    }
+
+    for (i, b) in path.bytes().enumerate() {
+        if b != b'/' {
+            continue;
+        }
+        let prefix = &path[..i];
+        let name = &path[i + 1..];
+        if !name.is_empty() && name.len() <= 100 && prefix.len() <= 155 {
+            return Some((prefix.to_string(), name.to_string()));
+        }
+    }
+
+    None
+}
+
+fn build_tar_header(name: &str, prefix: &str, size: u64, mode: u32, mtime: u64, typeflag: u8) -> [u8; 512] {
+    let mut header = [0u8; 512];
+    set_tar_field(&mut header[0..100], name.as_bytes());
+    set_tar_octal(&mut header[100..108], mode as u64);
+    set_tar_octal(&mut header[108..116], 0); // uid
+    set_tar_octal(&mut header[116..124], 0); // gid
+    set_tar_octal(&mut header[124..136], size);
+    set_tar_octal(&mut header[136..148], mtime);
+    for b in &mut header[148..156] {
+        *b = b' '; // chksum placeholder while computing the sum below
+    }
+    header[156] = typeflag;
+    set_tar_field(&mut header[257..263], b"ustar\0");
+    set_tar_field(&mut header[263..265], b"00");
+    set_tar_field(&mut header[345..500], prefix.as_bytes());
+
+    let checksum: u32 = header.iter().map(|&b| b as u32).sum();
+    set_tar_field(&mut header[148..156], format!("{:06o}\0 ", checksum).as_bytes());
+
+    header
+}
+
+fn pad_to_tar_block(writer: &mut impl Write, written: u64) -> io::Result<()> {
+    let remainder = written % TAR_BLOCK_SIZE;
+    if remainder != 0 {
+        writer.write_all(&vec![0u8; (TAR_BLOCK_SIZE - remainder) as usize])?;
+    }
+    Ok(())
+}
+
+fn entry_mode(meta: &fs::Metadata) -> u32 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        meta.permissions().mode() & 0o7777
+    }
+    #[cfg(not(unix))]
+    {
+        if meta.is_dir() { 0o755 } else { 0o644 }
+    }
+}
+
+fn write_tar_archive(dir: &Path, output: &Path) -> io::Result<u64> {
+    let mut entries = Vec::new();
+    let mut stack = vec![dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf())];
+    collect_archive_entries(dir, &mut stack, &mut entries)?;
+
+    let pb = ProgressBar::new(entries.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} {msg}")
+            .progress_chars("##-"),
+    );
+    pb.enable_steady_tick(100);
+
+    let mut writer = io::BufWriter::new(File::create(output)?);
+
+    for path in &entries {
+        let meta = fs::symlink_metadata(path)?;
+        let mut name = path.strip_prefix(dir).unwrap_or(path).display().to_string().replace('\\', "/");
+        let mtime = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let mode = entry_mode(&meta);
+
+        if meta.is_dir() && !name.ends_with('/') {
+            name.push('/');
+        }
+
+        let Some((prefix, name)) = split_tar_name(&name) else {
+            eprintln!("Skipping {} (not archived): path too long for the USTAR format", path.display());
+            pb.inc(1);
+            continue;
+        };
+
+        if meta.is_dir() {
+            let header = build_tar_header(&name, &prefix, 0, mode, mtime, b'5');
+            writer.write_all(&header)?;
+        } else {
+            let size = meta.len();
+            let header = build_tar_header(&name, &prefix, size, mode, mtime, b'0');
+            writer.write_all(&header)?;
+            io::copy(&mut File::open(path)?, &mut writer)?;
+            pad_to_tar_block(&mut writer, size)?;
+        }
+
+        pb.inc(1);
+    }
+
+    // Two zero blocks mark the end of the archive.
+    writer.write_all(&[0u8; (TAR_BLOCK_SIZE * 2) as usize])?;
+    writer.flush()?;
+    pb.finish_and_clear();
+
+    Ok(fs::metadata(output)?.len())
+}
+
+fn run_archive_mode(dir: &Path, output: &Path) -> io::Result<()> {
+    let archive_size = write_tar_archive(dir, output)?;
+    println!("Wrote {} ({})", output.display(), format_size(archive_size));
+    Ok(())
 }
 
 fn format_size(size: u64) -> String {
@@ -146,6 +848,68 @@ fn format_root_name(path: &Path) -> String {
     }
 }
 
+/// Metadata to use for the overview table's Modified/Perms/Owner columns.
+/// Follows symlinks so a symlinked directory or file shows the resolved
+/// target's permissions and owner (matching its already-target-derived
+/// `Size` column), falling back to the link's own metadata only when the
+/// link is broken or otherwise can't be followed.
+fn display_metadata(path: &Path) -> io::Result<fs::Metadata> {
+    fs::metadata(path).or_else(|_| fs::symlink_metadata(path))
+}
+
+fn format_mtime(meta: &fs::Metadata, pattern: &str) -> String {
+    match meta.modified() {
+        Ok(time) => {
+            let datetime: chrono::DateTime<chrono::Local> = time.into();
+            datetime.format(pattern).to_string()
+        }
+        Err(_) => "-".to_string(),
+    }
+}
+
+#[cfg(unix)]
+fn format_permissions(meta: &fs::Metadata) -> String {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = meta.permissions().mode();
+    let mut rendered = String::with_capacity(10);
+    rendered.push(if meta.is_dir() { 'd' } else { '-' });
+
+    for shift in [6, 3, 0] {
+        rendered.push(if (mode >> shift) & 0b100 != 0 { 'r' } else { '-' });
+        rendered.push(if (mode >> shift) & 0b010 != 0 { 'w' } else { '-' });
+        rendered.push(if (mode >> shift) & 0b001 != 0 { 'x' } else { '-' });
+    }
+
+    rendered
+}
+
+#[cfg(not(unix))]
+fn format_permissions(_meta: &fs::Metadata) -> String {
+    "-".to_string()
+}
+
+#[cfg(unix)]
+fn format_owner(meta: &fs::Metadata) -> String {
+    use std::os::unix::fs::MetadataExt;
+
+    let uid = meta.uid();
+    let gid = meta.gid();
+    let user = users::get_user_by_uid(uid)
+        .map(|u| u.name().to_string_lossy().to_string())
+        .unwrap_or_else(|| uid.to_string());
+    let group = users::get_group_by_gid(gid)
+        .map(|g| g.name().to_string_lossy().to_string())
+        .unwrap_or_else(|| gid.to_string());
+
+    format!("{}:{}", user, group)
+}
+
+#[cfg(not(unix))]
+fn format_owner(_meta: &fs::Metadata) -> String {
+    "-".to_string()
+}
+
 fn print_csv_table(file_path: &Path) -> Result<(), Box<dyn Error>> {
     let file = File::open(file_path)?;
     let mut rdr = ReaderBuilder::new().has_headers(true).from_reader(file);
@@ -184,10 +948,99 @@ fn print_file_content(path: &Path) -> io::Result<()> {
 fn main() -> io::Result<()> {
     // Get input or use the current directory 
     let args: Vec<String> = env::args().collect();
-    let folder_path = if args.len() > 1 {
-        Path::new(&args[1]) 
+
+    if args.len() > 2 && args[1] == "--duplicates" {
+        let dir = Path::new(&args[2]);
+        if let Err(e) = run_duplicates_mode(dir) {
+            eprintln!("Error scanning for duplicates: {}", e);
+            return Err(e);
+        }
+        return Ok(());
+    }
+
+    if args.len() > 2 && args[1] == "--archive" {
+        let dir = Path::new(&args[2]);
+        let output = args
+            .iter()
+            .position(|a| a == "-o")
+            .and_then(|i| args.get(i + 1))
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("archive.tar"));
+        if let Err(e) = run_archive_mode(dir, &output) {
+            eprintln!("Error creating archive: {}", e);
+            return Err(e);
+        }
+        return Ok(());
+    }
+
+    // Everything past this point accepts --exclude <glob> (repeatable),
+    // --hidden/--no-hidden, and the --time/--perms/--owner column toggles
+    // alongside the positional directory argument.
+    let mut walk_options = WalkOptions::default();
+    let mut positional = Vec::new();
+    let mut show_time = false;
+    let mut show_perms = false;
+    let mut show_owner = false;
+    let mut time_format = "%Y-%m-%d %H:%M".to_string();
+    let mut use_cache = false;
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--exclude" => {
+                if let Some(pattern) = args.get(i + 1) {
+                    walk_options.exclude.push(pattern.clone());
+                    i += 2;
+                } else {
+                    i += 1;
+                }
+            }
+            "--hidden" => {
+                walk_options.show_hidden = true;
+                i += 1;
+            }
+            "--no-hidden" => {
+                walk_options.show_hidden = false;
+                i += 1;
+            }
+            "--time" => {
+                show_time = true;
+                i += 1;
+            }
+            "--perms" => {
+                show_perms = true;
+                i += 1;
+            }
+            "--owner" => {
+                show_owner = true;
+                i += 1;
+            }
+            "--time-format" => {
+                if let Some(pattern) = args.get(i + 1) {
+                    time_format = pattern.clone();
+                    i += 2;
+                } else {
+                    i += 1;
+                }
+            }
+            "--cache" => {
+                use_cache = true;
+                i += 1;
+            }
+            "--no-cache" => {
+                use_cache = false;
+                i += 1;
+            }
+            other => {
+                positional.push(other.to_string());
+                i += 1;
+            }
+        }
+    }
+
+    let folder_path = if let Some(first) = positional.first() {
+        Path::new(first)
     } else {
-        Path::new(".") 
+        Path::new(".")
     };
 
   if folder_path.is_file() {
@@ -203,15 +1056,28 @@ fn main() -> io::Result<()> {
             }
         }
         return Ok(());
-    } 
+    }
 
-    let folder_sizes = match get_first_layer_full_sizes(folder_path) {
-        Ok(folder_sizes) => folder_sizes,
-        Err(e) => {
-            eprintln!("Error calculating folder size: {}", e);
-            return Err(e);
+    let mut size_cache = if use_cache { load_size_cache() } else { SizeCache::default() };
+    let cache_ref = if use_cache { Some(&size_cache) } else { None };
+
+    let (folder_sizes, symlink_issues, cache_updates) =
+        match get_first_layer_full_sizes(folder_path, &walk_options, cache_ref) {
+            Ok(result) => result,
+            Err(e) => {
+                eprintln!("Error calculating folder size: {}", e);
+                return Err(e);
+            }
+        };
+
+    if use_cache {
+        for (path, entry) in cache_updates {
+            size_cache.entries.insert(path, entry);
         }
-    };
+        if let Err(e) = save_size_cache(&size_cache) {
+            eprintln!("Warning: failed to write size cache: {}", e);
+        }
+    }
 
     // Calculate and display the total size of the root folder and its contents
     let total_size: u64 = folder_sizes.values().sum();
@@ -231,13 +1097,23 @@ fn main() -> io::Result<()> {
 
     // Create and configure the table
     let mut table = Table::new();
-    table.add_row(Row::new(vec![
+    let mut header = vec![
         Cell::new("#"),
         Cell::new("Path"),
-        Cell::new("Type"),        
+        Cell::new("Type"),
         Cell::new("Size"),
-        
-    ]));
+        Cell::new("Links"),
+    ];
+    if show_time {
+        header.push(Cell::new("Modified"));
+    }
+    if show_perms {
+        header.push(Cell::new("Perms"));
+    }
+    if show_owner {
+        header.push(Cell::new("Owner"));
+    }
+    table.add_row(Row::new(header));
 
     // Function to remove the current directory prefix
     let remove_prefix = |path: &Path| {
@@ -247,32 +1123,81 @@ fn main() -> io::Result<()> {
             .display().to_string()
     };
 
+    let links_note = |path: &Path| -> String {
+        match symlink_issues.get(path) {
+            Some(issues) => issues
+                .iter()
+                .map(|info| info.error_type.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            None => "-".to_string(),
+        }
+    };
+
     // Add folders to the table
     for (folder, size) in folders {
-        table.add_row(Row::new(vec![
+        let mut row = vec![
             Cell::new("üìÅ"),
             Cell::new(&remove_prefix(folder)),
             Cell::new("-"),
             Cell::new(&format_size(*size)),
-
-        ]));
+            Cell::new(&links_note(folder)),
+        ];
+        if let Ok(meta) = display_metadata(folder) {
+            if show_time {
+                row.push(Cell::new(&format_mtime(&meta, &time_format)));
+            }
+            if show_perms {
+                row.push(Cell::new(&format_permissions(&meta)));
+            }
+            if show_owner {
+                row.push(Cell::new(&format_owner(&meta)));
+            }
+        }
+        table.add_row(Row::new(row));
     }
 
     // Add files to the table
     for (file, size) in files {
-        table.add_row(Row::new(vec![
+        let mut row = vec![
             Cell::new("üìÑ"),
             Cell::new(&remove_prefix(file)),
             Cell::new(&get_file_type(file)),
             Cell::new(&format_size(*size)),
-            
-        ]));
+            Cell::new(&links_note(file)),
+        ];
+        if let Ok(meta) = display_metadata(file) {
+            if show_time {
+                row.push(Cell::new(&format_mtime(&meta, &time_format)));
+            }
+            if show_perms {
+                row.push(Cell::new(&format_permissions(&meta)));
+            }
+            if show_owner {
+                row.push(Cell::new(&format_owner(&meta)));
+            }
+        }
+        table.add_row(Row::new(row));
     }
 
     // Print the table
     table.printstd();
     println!("");
 
+    if !symlink_issues.is_empty() {
+        println!(
+            "Note: {} entr{} had symlink issues and were not fully followed:",
+            symlink_issues.len(),
+            if symlink_issues.len() == 1 { "y" } else { "ies" }
+        );
+        for (path, issues) in &symlink_issues {
+            for info in issues {
+                println!("  {} -> {} ({})", remove_prefix(path), info.destination.display(), info.error_type);
+            }
+        }
+        println!();
+    }
+
     Ok(())
 }
 
@@ -326,8 +1251,11 @@ mod tests {
         sub_file.write_all(b"Hello, sub world!").unwrap(); // 17 bytes
 
         // Calculate the size of the temp directory
-        let size = calculate_total_size(&temp_dir).unwrap();
+        let mut stack = Vec::new();
+        let mut symlinks = Vec::new();
+        let size = calculate_total_size(&temp_dir, &temp_dir, &mut stack, &mut symlinks, &WalkOptions::default()).unwrap();
         assert_eq!(size, 13 + 17); // 13 bytes + 17 bytes
+        assert!(symlinks.is_empty());
 
         // Clean up
         fs::remove_dir_all(temp_dir).unwrap();
@@ -350,10 +1278,280 @@ mod tests {
         sub_file.write_all(b"Hello, sub world!").unwrap(); // 17 bytes
 
         // Get the first layer folder sizes
-        let folder_sizes = get_first_layer_full_sizes(&temp_dir).unwrap();
+        let (folder_sizes, symlink_issues, _) = get_first_layer_full_sizes(&temp_dir, &WalkOptions::default(), None).unwrap();
 
         assert_eq!(folder_sizes.get(&file_path).unwrap(), &13); // File size
         assert_eq!(folder_sizes.get(&sub_dir).unwrap(), &(17)); // Size of sub_dir's content
+        assert!(symlink_issues.is_empty());
+
+        // Clean up
+        fs::remove_dir_all(temp_dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_calculate_total_size_detects_symlink_cycle() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = env::temp_dir().join("test_symlink_cycle");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir(&temp_dir).unwrap();
+
+        let sub_dir = temp_dir.join("sub_dir");
+        fs::create_dir(&sub_dir).unwrap();
+        symlink(&temp_dir, sub_dir.join("back_to_root")).unwrap();
+
+        let mut stack = vec![temp_dir.canonicalize().unwrap()];
+        let mut symlinks = Vec::new();
+        calculate_total_size(&temp_dir, &temp_dir, &mut stack, &mut symlinks, &WalkOptions::default()).unwrap();
+
+        assert_eq!(symlinks.len(), 1);
+        assert!(matches!(symlinks[0].1.error_type, SymlinkErrorType::InfiniteRecursion));
+
+        // Clean up
+        fs::remove_dir_all(temp_dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_calculate_total_size_reports_broken_symlink() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = env::temp_dir().join("test_broken_symlink");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir(&temp_dir).unwrap();
+        symlink(temp_dir.join("does_not_exist"), temp_dir.join("dangling")).unwrap();
+
+        let mut stack = Vec::new();
+        let mut symlinks = Vec::new();
+        calculate_total_size(&temp_dir, &temp_dir, &mut stack, &mut symlinks, &WalkOptions::default()).unwrap();
+
+        assert_eq!(symlinks.len(), 1);
+        assert!(matches!(symlinks[0].1.error_type, SymlinkErrorType::BrokenTarget));
+
+        // Clean up
+        fs::remove_dir_all(temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_find_duplicate_files() {
+        let temp_dir = env::temp_dir().join("test_find_duplicate_files");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir(&temp_dir).unwrap();
+
+        let mut a = File::create(temp_dir.join("a.txt")).unwrap();
+        a.write_all(b"duplicate content").unwrap();
+        let mut b = File::create(temp_dir.join("b.txt")).unwrap();
+        b.write_all(b"duplicate content").unwrap();
+        let mut c = File::create(temp_dir.join("c.txt")).unwrap();
+        c.write_all(b"unique content here").unwrap();
+
+        let groups = find_duplicate_files(&temp_dir).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].len(), 2);
+
+        // Clean up
+        fs::remove_dir_all(temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_tar_archive() {
+        let temp_dir = env::temp_dir().join("test_write_tar_archive");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir(&temp_dir).unwrap();
+
+        let mut file = File::create(temp_dir.join("file1.txt")).unwrap();
+        file.write_all(b"Hello, world!").unwrap(); // 13 bytes
+
+        let archive_path = env::temp_dir().join("test_write_tar_archive.tar");
+        let archive_size = write_tar_archive(&temp_dir, &archive_path).unwrap();
+
+        // One 512-byte header, one padded data block, two trailing zero blocks.
+        assert_eq!(archive_size, 512 * 4);
+        assert_eq!(archive_size % 512, 0);
+
+        let contents = fs::read(&archive_path).unwrap();
+        assert!(contents.starts_with(b"file1.txt"));
+
+        // Clean up
+        fs::remove_dir_all(temp_dir).unwrap();
+        fs::remove_file(archive_path).unwrap();
+    }
+
+    #[test]
+    fn test_split_tar_name() {
+        // Short paths need no split.
+        assert_eq!(split_tar_name("file.txt"), Some((String::new(), "file.txt".to_string())));
+
+        // A path over 100 bytes splits at a `/` so both pieces fit.
+        let deep = format!("{}/file.txt", "a".repeat(150));
+        let (prefix, name) = split_tar_name(&deep).unwrap();
+        assert_eq!(name, "file.txt");
+        assert_eq!(prefix, "a".repeat(150));
+        assert_eq!(format!("{}/{}", prefix, name), deep);
+
+        // A single path component longer than 100 bytes has no valid split.
+        let unsplittable = "a".repeat(120);
+        assert!(split_tar_name(&unsplittable).is_none());
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*.tmp", "cache.tmp"));
+        assert!(!glob_match("*.tmp", "cache.txt"));
+        assert!(glob_match("node_modules", "node_modules"));
+        assert!(!glob_match("*.tmp", "a/b.tmp")); // '*' doesn't cross '/'
+        assert!(glob_match("**/b.tmp", "a/b.tmp"));
+        assert!(glob_match("**/b.tmp", "b.tmp"));
+    }
+
+    #[test]
+    fn test_get_first_layer_full_sizes_respects_exclude_and_hidden() {
+        let temp_dir = env::temp_dir().join("test_walk_options_filtering");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir(&temp_dir).unwrap();
+
+        File::create(temp_dir.join("keep.txt")).unwrap().write_all(b"hi").unwrap();
+        File::create(temp_dir.join("skip.tmp")).unwrap().write_all(b"hi").unwrap();
+        File::create(temp_dir.join(".hidden")).unwrap().write_all(b"hi").unwrap();
+
+        let options = WalkOptions { exclude: vec!["*.tmp".to_string()], show_hidden: false };
+        let (folder_sizes, _, _) = get_first_layer_full_sizes(&temp_dir, &options, None).unwrap();
+
+        assert!(folder_sizes.contains_key(&temp_dir.join("keep.txt")));
+        assert!(!folder_sizes.contains_key(&temp_dir.join("skip.tmp")));
+        assert!(!folder_sizes.contains_key(&temp_dir.join(".hidden")));
+
+        // Clean up
+        fs::remove_dir_all(temp_dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_format_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = env::temp_dir().join("test_format_permissions");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir(&temp_dir).unwrap();
+
+        let file_path = temp_dir.join("file1.txt");
+        File::create(&file_path).unwrap();
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let meta = fs::metadata(&file_path).unwrap();
+        assert_eq!(format_permissions(&meta), "-rw-r--r--");
+
+        // Clean up
+        fs::remove_dir_all(temp_dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_display_metadata_follows_symlink_to_restricted_dir() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = env::temp_dir().join("test_display_metadata_symlink");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir(&temp_dir).unwrap();
+
+        let target_dir = temp_dir.join("target");
+        fs::create_dir(&target_dir).unwrap();
+        fs::set_permissions(&target_dir, fs::Permissions::from_mode(0o700)).unwrap();
+
+        let link = temp_dir.join("link");
+        std::os::unix::fs::symlink(&target_dir, &link).unwrap();
+
+        // The symlink itself is always wide open; a caller asking for the
+        // resolved entry's permissions must see the target's, not the link's.
+        let meta = display_metadata(&link).unwrap();
+        assert_eq!(format_permissions(&meta), "drwx------");
+
+        // A broken link has nothing to follow to, so fall back to its own metadata.
+        let broken = temp_dir.join("broken");
+        std::os::unix::fs::symlink(temp_dir.join("does_not_exist"), &broken).unwrap();
+        let meta = display_metadata(&broken).unwrap();
+        assert!(meta.file_type().is_symlink());
+
+        // Clean up
+        fs::set_permissions(&target_dir, fs::Permissions::from_mode(0o755)).unwrap();
+        fs::remove_dir_all(temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_get_first_layer_full_sizes_reuses_cached_entry() {
+        let temp_dir = env::temp_dir().join("test_size_cache_reuse");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir(&temp_dir).unwrap();
+
+        let sub_dir = temp_dir.join("sub_dir");
+        let deep_dir = sub_dir.join("deep");
+        fs::create_dir_all(&deep_dir).unwrap();
+        File::create(deep_dir.join("file.txt")).unwrap().write_all(b"Hello, world!").unwrap(); // 13 bytes
+
+        let canon_sub_dir = sub_dir.canonicalize().unwrap();
+        let mtime = subtree_latest_mtime(&sub_dir, &temp_dir, &WalkOptions::default()).unwrap();
+
+        let mut cache = SizeCache::default();
+        cache.entries.insert(
+            canon_sub_dir.clone(),
+            CacheEntry { mtime, options_key: WalkOptions::default().cache_key(), total_size: 13 },
+        );
+
+        // No cache requested: always recomputes and never records an update.
+        let (folder_sizes, _, updates) = get_first_layer_full_sizes(&temp_dir, &WalkOptions::default(), None).unwrap();
+        assert_eq!(folder_sizes.get(&sub_dir).unwrap(), &13);
+        assert!(updates.is_empty());
+
+        // A cache entry whose recursive signature still matches is reused verbatim.
+        let (folder_sizes, _, updates) = get_first_layer_full_sizes(&temp_dir, &WalkOptions::default(), Some(&cache)).unwrap();
+        assert_eq!(folder_sizes.get(&sub_dir).unwrap(), &13);
+        assert!(updates.is_empty());
+
+        // Editing a file several levels deep doesn't touch sub_dir's own mtime,
+        // but it must still bust the cache instead of silently returning a stale total.
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        let grown = b"This file has grown considerably larger than before.";
+        File::create(deep_dir.join("file.txt")).unwrap().write_all(grown).unwrap();
+
+        let (folder_sizes, _, updates) = get_first_layer_full_sizes(&temp_dir, &WalkOptions::default(), Some(&cache)).unwrap();
+        assert_eq!(folder_sizes.get(&sub_dir).unwrap(), &(grown.len() as u64));
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].0, canon_sub_dir);
+        assert_eq!(updates[0].1.total_size, grown.len() as u64);
+
+        // Clean up
+        fs::remove_dir_all(temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_get_first_layer_full_sizes_invalidates_cache_on_option_change() {
+        let temp_dir = env::temp_dir().join("test_size_cache_options");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir(&temp_dir).unwrap();
+
+        let sub_dir = temp_dir.join("sub_dir");
+        fs::create_dir(&sub_dir).unwrap();
+        File::create(sub_dir.join("keep.txt")).unwrap().write_all(b"keep").unwrap(); // 4 bytes
+        File::create(sub_dir.join("skip.log")).unwrap().write_all(b"skip me").unwrap(); // 7 bytes
+
+        let no_exclude = WalkOptions::default();
+        let with_exclude = WalkOptions { exclude: vec!["*.log".to_string()], show_hidden: false };
+
+        // Populate the cache under the unfiltered options.
+        let (folder_sizes, _, updates) = get_first_layer_full_sizes(&temp_dir, &no_exclude, Some(&SizeCache::default())).unwrap();
+        assert_eq!(folder_sizes.get(&sub_dir).unwrap(), &11);
+        let mut cache = SizeCache::default();
+        for (path, entry) in updates {
+            cache.entries.insert(path, entry);
+        }
+
+        // Re-running with a different exclude set must not reuse that cached
+        // total: the filtered size is smaller and must be recomputed fresh.
+        let (folder_sizes, _, updates) = get_first_layer_full_sizes(&temp_dir, &with_exclude, Some(&cache)).unwrap();
+        assert_eq!(folder_sizes.get(&sub_dir).unwrap(), &4);
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].1.total_size, 4);
 
         // Clean up
         fs::remove_dir_all(temp_dir).unwrap();